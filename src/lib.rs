@@ -13,6 +13,18 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(all(test, feature = "std"))]
+mod test_support;
+
 mod slice;
 
+#[cfg(feature = "alloc")]
+pub mod rc;
+
 pub use slice::HeaderSlice;
+
+#[cfg(feature = "std")]
+pub use slice::{AnyBitPattern, NoPadding};
+
+#[cfg(feature = "alloc")]
+pub use rc::{HeaderArc, HeaderRc};
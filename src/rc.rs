@@ -0,0 +1,466 @@
+use core::{
+    cell::Cell,
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    ptr::{self, NonNull},
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::HeaderSlice;
+
+mod private {
+    use core::{cell::Cell, sync::atomic::AtomicUsize};
+
+    pub trait Sealed {}
+    impl Sealed for Cell<usize> {}
+    impl Sealed for AtomicUsize {}
+}
+
+/// A strong/weak reference count that can be shared behind a single
+/// allocation, abstracting over atomic and non-atomic counting.
+///
+/// This is sealed: it's only implemented for [`AtomicUsize`] and
+/// [`Cell<usize>`], the count types backing [`HeaderArc`] and [`HeaderRc`]
+/// respectively, and exists to let `HeaderRcBase` share its allocation,
+/// clone, and drop logic between the two.
+pub trait Count: private::Sealed {
+    fn new(value: usize) -> Self;
+
+    /// Increments the count.
+    fn increment(&self);
+
+    /// Decrements the count, returning `true` if it reached zero.
+    fn decrement(&self) -> bool;
+}
+
+impl Count for Cell<usize> {
+    #[inline]
+    fn new(value: usize) -> Self {
+        Cell::new(value)
+    }
+
+    #[inline]
+    fn increment(&self) {
+        self.set(self.get() + 1);
+    }
+
+    #[inline]
+    fn decrement(&self) -> bool {
+        let count = self.get() - 1;
+        self.set(count);
+        count == 0
+    }
+}
+
+impl Count for AtomicUsize {
+    #[inline]
+    fn new(value: usize) -> Self {
+        AtomicUsize::new(value)
+    }
+
+    #[inline]
+    fn increment(&self) {
+        self.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn decrement(&self) -> bool {
+        // Matches the `Arc` drop protocol: a `Release` decrement paired with
+        // an `Acquire` fence on the thread that observes the count reaching
+        // zero, so every prior access to the shared data happens-before the
+        // deallocation below.
+        if self.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+}
+
+/// The leading, fixed-size part of a [`HeaderArc`]/[`HeaderRc`] allocation:
+/// the reference counts and slice length, with a [`HeaderSlice<H, T>`]-shaped
+/// region (header, then trailing `[T]`) placed right after it in memory.
+///
+/// Splitting the counts out into their own `Sized` type, rather than folding
+/// them into the header as a prior version of this module did, is what lets
+/// `HeaderRcBase` hand out a sound `&HeaderSlice<H, T>`: the data region
+/// starting at [`HeaderRcBase::data_offset`] now has *exactly* the layout
+/// `HeaderSlice<H, T>` itself would compute (header at offset `0`, slice at
+/// `HeaderSlice::<H, T>::items_offset()`), so [`HeaderSlice::from_raw_parts`]
+/// can be used on it directly.
+#[repr(C)]
+struct HeaderRcCounts<C> {
+    strong: C,
+    weak: C,
+    length: usize,
+}
+
+/// A thin, reference-counted pointer to a [`HeaderSlice<H, T>`], in the
+/// spirit of servo's `ThinArc`.
+///
+/// The whole `{ strong_count, weak_count, length, header, slice }` lives in a
+/// single heap allocation, so `HeaderArc<H, T>`/`HeaderRc<H, T>` are
+/// pointer-sized even though `HeaderSlice<H, T>` is a dynamically-sized type.
+/// `ptr` points past the counts, directly at the `HeaderSlice<H, T>`-shaped
+/// data region, so it can be handed to [`HeaderSlice::from_raw_parts`]
+/// as-is; the counts are recovered by walking backwards from `ptr` instead.
+///
+/// `C` selects the counting strategy: [`AtomicUsize`] for [`HeaderArc`]
+/// (shareable across threads) or [`Cell<usize>`] for [`HeaderRc`]
+/// (single-threaded, cheaper to update).
+pub struct HeaderRcBase<C: Count, H, T> {
+    ptr: NonNull<H>,
+    phantom: PhantomData<(C, HeaderSlice<H, T>)>,
+}
+
+/// A thin, atomically reference-counted pointer to a [`HeaderSlice<H, T>`].
+///
+/// See [`HeaderRcBase`] for details; this is the `Send + Sync` flavor,
+/// analogous to [`Arc`](alloc::sync::Arc).
+pub type HeaderArc<H, T> = HeaderRcBase<AtomicUsize, H, T>;
+
+/// A thin, single-threaded reference-counted pointer to a
+/// [`HeaderSlice<H, T>`].
+///
+/// See [`HeaderRcBase`] for details; this is the non-atomic flavor,
+/// analogous to [`Rc`](alloc::rc::Rc).
+pub type HeaderRc<H, T> = HeaderRcBase<Cell<usize>, H, T>;
+
+// SAFETY: `HeaderRcBase<C, H, T>` owns its allocation and only exposes
+// shared access to `H`/`T` through `&HeaderSlice<H, T>`, so it can be sent or
+// shared across threads exactly when `H` and `T` can be, *and* when the
+// counter itself is safe to share (i.e. `C: Sync`, which is true for
+// `AtomicUsize` but false for `Cell<usize>`).
+unsafe impl<C, H, T> Send for HeaderRcBase<C, H, T>
+where
+    C: Count + Send + Sync,
+    H: Send + Sync,
+    T: Send + Sync,
+{
+}
+
+unsafe impl<C, H, T> Sync for HeaderRcBase<C, H, T>
+where
+    C: Count + Send + Sync,
+    H: Send + Sync,
+    T: Send + Sync,
+{
+}
+
+impl<C: Count, H, T> HeaderRcBase<C, H, T> {
+    /// Returns the combined layout of `{ HeaderRcCounts<C>, HeaderSlice<H, T> }`
+    /// for `len` trailing elements, along with the byte offset of the
+    /// `HeaderSlice<H, T>`-shaped region within it.
+    ///
+    /// The offset only depends on `HeaderSlice::<H, T>::align()`, not on
+    /// `len`, so [`Self::data_offset`] can read it off using any length.
+    #[inline]
+    fn layout_and_data_offset(len: usize) -> (Layout, usize) {
+        let counts = Layout::new::<HeaderRcCounts<C>>();
+        let data = HeaderSlice::<H, T>::layout_for_len(len);
+
+        let (layout, offset) = counts.extend(data).expect("invalid layout");
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Returns the byte offset from the allocation base to the
+    /// `HeaderSlice<H, T>`-shaped data region, i.e. to `ptr`.
+    #[inline]
+    fn data_offset() -> usize {
+        Self::layout_and_data_offset(0).1
+    }
+
+    /// Creates a new reference-counted header-slice by allocating space for
+    /// `header` followed by a clone of each element of `items`.
+    #[inline]
+    pub fn from_header_and_slice(header: H, items: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_header_and_iter(header, items.iter().cloned())
+    }
+
+    /// Creates a new reference-counted header-slice by allocating space for
+    /// `header` followed by the elements yielded by `items`.
+    pub fn from_header_and_iter<I>(header: H, items: I) -> Self
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        let len = items.len();
+        let (layout, data_offset) = Self::layout_and_data_offset(len);
+
+        // SAFETY: `layout` always has nonzero size, since it holds at least
+        // a `HeaderRcCounts<C>`.
+        let base = unsafe { alloc(layout) };
+        if base.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        // SAFETY: `base` is non-null, freshly allocated, and large enough to
+        // hold the counts at offset `0`.
+        unsafe {
+            ptr::write(
+                base as *mut HeaderRcCounts<C>,
+                HeaderRcCounts {
+                    strong: C::new(1),
+                    weak: C::new(1),
+                    length: len,
+                },
+            );
+        }
+
+        let data_ptr = unsafe { base.add(data_offset) as *mut H };
+
+        // Frees the allocation and drops whatever has been initialized so
+        // far if a `Clone`/`Iterator` impl panics partway through.
+        let mut guard = RawPartsGuard::<H, T> {
+            base,
+            layout,
+            data_ptr,
+            header_written: false,
+            initialized: 0,
+            phantom: PhantomData,
+        };
+
+        // SAFETY: `data_ptr` is within the allocation and large enough to
+        // hold `header` at its start.
+        unsafe { ptr::write(data_ptr, header) };
+        guard.header_written = true;
+
+        let items_ptr =
+            unsafe { (data_ptr as *mut u8).add(HeaderSlice::<H, T>::items_offset()) as *mut T };
+        for (i, item) in items.enumerate() {
+            assert!(i < len, "iterator yielded more elements than its length");
+
+            // SAFETY: `i < len`, so `items_ptr.add(i)` is within the
+            // allocation and hasn't been written yet.
+            unsafe { ptr::write(items_ptr.add(i), item) };
+            guard.initialized = i + 1;
+        }
+        assert_eq!(guard.initialized, len, "iterator yielded fewer elements than its length");
+
+        mem::forget(guard);
+
+        HeaderRcBase {
+            // SAFETY: `data_ptr` is non-null, being offset from the
+            // non-null `base`.
+            ptr: unsafe { NonNull::new_unchecked(data_ptr) },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the counts and length preceding the data
+    /// region at `self.ptr`.
+    #[inline]
+    fn counts(&self) -> &HeaderRcCounts<C> {
+        // SAFETY: `self` holds a strong reference, so the allocation is
+        // still live; the counts sit exactly `Self::data_offset()` bytes
+        // before `self.ptr`, by construction in `from_header_and_iter`.
+        unsafe {
+            let base = (self.ptr.as_ptr() as *const u8).sub(Self::data_offset());
+            &*(base as *const HeaderRcCounts<C>)
+        }
+    }
+}
+
+impl<C: Count, H, T> Deref for HeaderRcBase<C, H, T> {
+    type Target = HeaderSlice<H, T>;
+
+    #[inline]
+    fn deref(&self) -> &HeaderSlice<H, T> {
+        let len = self.counts().length;
+
+        // SAFETY: `self.ptr` points to a live, fully-initialized
+        // `HeaderSlice<H, T>`-shaped region of `len` trailing elements,
+        // allocated by `from_header_and_iter`.
+        unsafe { HeaderSlice::from_raw_parts(self.ptr.as_ptr(), len) }
+    }
+}
+
+impl<C: Count, H, T> Clone for HeaderRcBase<C, H, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.counts().strong.increment();
+
+        HeaderRcBase {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Count, H, T> Drop for HeaderRcBase<C, H, T> {
+    fn drop(&mut self) {
+        if !self.counts().strong.decrement() {
+            return;
+        }
+
+        let len = self.counts().length;
+
+        unsafe {
+            // Drop the header and every trailing element in place before
+            // touching the allocation's weak count.
+            let hs = HeaderSlice::from_raw_parts_mut(self.ptr.as_ptr(), len);
+            ptr::drop_in_place(hs as *mut HeaderSlice<H, T>);
+
+            if self.counts().weak.decrement() {
+                let base = (self.ptr.as_ptr() as *mut u8).sub(Self::data_offset());
+                let (layout, _) = Self::layout_and_data_offset(len);
+                dealloc(base, layout);
+            }
+        }
+    }
+}
+
+impl<C: Count, H, T> fmt::Debug for HeaderRcBase<C, H, T>
+where
+    H: fmt::Debug,
+    T: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// Frees a `HeaderRcBase` allocation and drops whichever parts of it have
+/// been initialized so far, used to stay panic-safe while filling the
+/// allocation made by [`HeaderRcBase::from_header_and_iter`].
+struct RawPartsGuard<H, T> {
+    base: *mut u8,
+    layout: Layout,
+    data_ptr: *mut H,
+    header_written: bool,
+    initialized: usize,
+    phantom: PhantomData<(H, T)>,
+}
+
+impl<H, T> Drop for RawPartsGuard<H, T> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.header_written {
+                ptr::drop_in_place(self.data_ptr);
+            }
+
+            let items_ptr =
+                (self.data_ptr as *mut u8).add(HeaderSlice::<H, T>::items_offset()) as *mut T;
+            for i in 0..self.initialized {
+                ptr::drop_in_place(items_ptr.add(i));
+            }
+
+            dealloc(self.base, self.layout);
+        }
+    }
+}
+
+impl<H> From<Arc<H>> for HeaderArc<H, H>
+where
+    H: Clone,
+{
+    /// Converts an `Arc<H>` into an empty (zero-length-slice) `HeaderArc`.
+    ///
+    /// This always allocates a new, separate `HeaderArc`; if `header` has
+    /// other `Arc` owners, its value is cloned rather than moved out.
+    #[inline]
+    fn from(header: Arc<H>) -> Self {
+        match Arc::try_unwrap(header) {
+            Ok(header) => Self::from_header_and_slice(header, &[]),
+            Err(header) => Self::from_header_and_slice((*header).clone(), &[]),
+        }
+    }
+}
+
+impl<H> From<Rc<H>> for HeaderRc<H, H>
+where
+    H: Clone,
+{
+    /// Converts an `Rc<H>` into an empty (zero-length-slice) `HeaderRc`.
+    ///
+    /// This always allocates a new, separate `HeaderRc`; if `header` has
+    /// other `Rc` owners, its value is cloned rather than moved out.
+    #[inline]
+    fn from(header: Rc<H>) -> Self {
+        match Rc::try_unwrap(header) {
+            Ok(header) => Self::from_header_and_slice(header, &[]),
+            Err(header) => Self::from_header_and_slice((*header).clone(), &[]),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{HeaderArc, HeaderRc};
+
+    // Over-aligned relative to the trailing slice element types below, so a
+    // mismatch between `HeaderRcCounts<C>`'s layout and `HeaderSlice<H, T>`'s
+    // own layout would show up as a misplaced data region.
+    #[repr(align(16))]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct OverAligned(u64);
+
+    #[test]
+    fn deref_exposes_header_and_slice() {
+        let _guard = crate::test_support::lock();
+
+        let rc = HeaderRc::<OverAligned, u16>::from_header_and_slice(OverAligned(1), &[10, 20, 30]);
+        assert_eq!(rc.header, OverAligned(1));
+        assert_eq!(&rc.slice, &[10, 20, 30]);
+    }
+
+    #[test]
+    fn clone_shares_the_allocation_until_the_last_reference_drops() {
+        let _guard = crate::test_support::lock();
+
+        let arc = HeaderArc::<OverAligned, u8>::from_header_and_slice(OverAligned(5), &[1, 2, 3]);
+        let arc2 = arc.clone();
+
+        drop(arc);
+
+        // `arc2` must still be valid: the allocation is only freed once the
+        // last strong reference drops.
+        assert_eq!(arc2.header, OverAligned(5));
+        assert_eq!(&arc2.slice, &[1, 2, 3]);
+
+        drop(arc2);
+    }
+
+    #[test]
+    fn strong_count_refcounting_drops_the_header_exactly_once() {
+        let _guard = crate::test_support::lock();
+
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounted;
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        impl Drop for DropCounted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        DROPS.store(0, Ordering::SeqCst);
+
+        let arc = HeaderArc::<DropCounted, u8>::from_header_and_slice(DropCounted, &[1, 2, 3]);
+        let arc2 = arc.clone();
+        let arc3 = arc2.clone();
+
+        drop(arc);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+        drop(arc2);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+        drop(arc3);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -4,7 +4,13 @@ use core::{
 };
 
 #[cfg(feature = "alloc")]
-use alloc::boxed::Box;
+use alloc::{
+    alloc::{alloc, alloc_zeroed, dealloc, handle_alloc_error, Layout},
+    boxed::Box,
+};
+
+#[cfg(feature = "std")]
+use std::io::{IoSlice, IoSliceMut};
 
 /// A dynamically-sized view into a contiguous header and trailing sequence.
 #[repr(C)]
@@ -66,10 +72,306 @@ impl<H, T> HeaderSlice<H, T> {
 
         slice_addr - base_addr
     }
+
+    /// Returns the layout for a header-slice allocation with `len` trailing
+    /// elements.
+    ///
+    /// This is padded to `align()`, matching `Layout::for_value` of the
+    /// resulting `HeaderSlice<H, T>` — required so that the size passed to
+    /// `alloc` equals the size `Box`'s `Drop` later passes to `dealloc`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn layout_for_len(len: usize) -> Layout {
+        let items_size = len
+            .checked_mul(mem::size_of::<T>())
+            .expect("capacity overflow");
+
+        let size = Self::items_offset()
+            .checked_add(items_size)
+            .expect("capacity overflow");
+
+        Layout::from_size_align(size, Self::align())
+            .expect("invalid layout")
+            .pad_to_align()
+    }
+
+    /// Like [`layout_for_len`], but reports overflow instead of panicking.
+    ///
+    /// As with [`layout_for_len`], the result is padded to `align()` so the
+    /// size used to allocate matches `Layout::for_value` of the resulting
+    /// `HeaderSlice<H, T>`.
+    ///
+    /// [`layout_for_len`]: Self::layout_for_len
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn try_layout_for_len(len: usize) -> Result<Layout, TryReserveError> {
+        let items_size = len
+            .checked_mul(mem::size_of::<T>())
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        let size = Self::items_offset()
+            .checked_add(items_size)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        Layout::from_size_align(size, Self::align())
+            .map(|layout| layout.pad_to_align())
+            .map_err(|_| TryReserveError::CapacityOverflow)
+    }
+}
+
+/// Allocates `layout`, aborting via [`handle_alloc_error`] on failure.
+///
+/// Unlike calling [`alloc`] directly, this is sound even when `layout` has
+/// size `0` — which a header-slice layout can when `H` is a zero-sized type
+/// and there are no trailing elements. Such a `layout` never reaches the
+/// global allocator; a dangling, correctly-aligned pointer is returned
+/// instead, mirroring how `Box` handles zero-sized allocations.
+#[cfg(feature = "alloc")]
+#[inline]
+unsafe fn alloc_for_layout(layout: Layout) -> *mut u8 {
+    if layout.size() == 0 {
+        layout.align() as *mut u8
+    } else {
+        alloc(layout)
+    }
+}
+
+/// Like [`alloc_for_layout`], but zeroes the allocation.
+#[cfg(feature = "alloc")]
+#[inline]
+unsafe fn alloc_zeroed_for_layout(layout: Layout) -> *mut u8 {
+    if layout.size() == 0 {
+        layout.align() as *mut u8
+    } else {
+        alloc_zeroed(layout)
+    }
+}
+
+/// Deallocates `ptr`, previously returned by [`alloc_for_layout`] or
+/// [`alloc_zeroed_for_layout`] with the same `layout`.
+///
+/// Skips the call to [`dealloc`] when `layout` has size `0`, since no such
+/// allocation ever reached the global allocator in that case.
+#[cfg(feature = "alloc")]
+#[inline]
+unsafe fn dealloc_for_layout(ptr: *mut u8, layout: Layout) {
+    if layout.size() != 0 {
+        dealloc(ptr, layout);
+    }
+}
+
+/// The reason a fallible header-slice allocation (the `try_*` constructors)
+/// was rejected.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// Computing the allocation's layout overflowed, e.g. `len * size_of::<T>()`
+    /// exceeds `usize`, or the resulting size exceeds `isize::MAX`.
+    CapacityOverflow,
+
+    /// The allocator refused to allocate the given layout.
+    AllocError {
+        /// The layout that was requested from the allocator.
+        layout: Layout,
+    },
+}
+
+/// The error returned by the `try_*` header-slice constructors: the reason
+/// the allocation was rejected, together with the input it would have
+/// consumed, so that nothing the caller passed in is leaked.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryAllocError<V> {
+    /// The value the constructor would have consumed.
+    pub value: V,
+
+    /// The reason the allocation was rejected.
+    pub error: TryReserveError,
+}
+
+impl<H, T> HeaderSlice<H, T> {
+    /// Attempts to create a boxed header-slice from just `header`, without
+    /// aborting on allocation failure.
+    ///
+    /// Unlike [`from_boxed_header`], this always allocates fresh storage
+    /// sized for the full header-slice layout (`header` is relocated into
+    /// it), rather than reusing `header`'s own allocation, so the resulting
+    /// layout is correct even when `T` has a larger alignment than `H`.
+    ///
+    /// [`from_boxed_header`]: Self::from_boxed_header
+    #[cfg(feature = "alloc")]
+    pub fn try_from_boxed_header(header: Box<H>) -> Result<Box<Self>, TryAllocError<Box<H>>> {
+        let layout = match Self::try_layout_for_len(0) {
+            Ok(layout) => layout,
+            Err(error) => return Err(TryAllocError { value: header, error }),
+        };
+
+        // SAFETY: `alloc_for_layout` returns a dangling pointer instead of
+        // calling into the allocator when `layout` has size `0` (e.g. a
+        // zero-sized `H` with no trailing elements).
+        let ptr = unsafe { alloc_for_layout(layout) };
+        if ptr.is_null() {
+            return Err(TryAllocError {
+                value: header,
+                error: TryReserveError::AllocError { layout },
+            });
+        }
+
+        let header_ptr = Box::into_raw(header);
+        // SAFETY: `header_ptr` is valid for `size_of::<H>()` bytes, and
+        // `ptr` was just allocated with room for at least that many at
+        // offset `0`; relocating the bytes and freeing the old allocation
+        // (without running `H`'s destructor) is equivalent to moving
+        // `*header`. `dealloc_for_layout` skips the actual `dealloc` call
+        // when `H` is a zero-sized type, matching how `Box<H>` never truly
+        // allocated in that case.
+        unsafe {
+            ptr::copy_nonoverlapping(header_ptr as *const u8, ptr, mem::size_of::<H>());
+            dealloc_for_layout(header_ptr as *mut u8, Layout::new::<H>());
+        }
+
+        // SAFETY: `ptr` points to a freshly-initialized header-slice with
+        // zero trailing elements, allocated with `Self::layout_for_len(0)`.
+        Ok(unsafe { Self::boxed_from_raw_parts(ptr as *mut H, 0) })
+    }
+
+    /// Attempts to create a boxed header-slice by allocating space for
+    /// `header` followed by a clone of each element of `items`, without
+    /// aborting on allocation failure.
+    #[cfg(feature = "alloc")]
+    pub fn try_from_header_and_slice(
+        header: H,
+        items: &[T],
+    ) -> Result<Box<Self>, TryAllocError<H>>
+    where
+        T: Clone,
+    {
+        let len = items.len();
+        let layout = match Self::try_layout_for_len(len) {
+            Ok(layout) => layout,
+            Err(error) => return Err(TryAllocError { value: header, error }),
+        };
+
+        // SAFETY: `alloc_for_layout` returns a dangling pointer instead of
+        // calling into the allocator when `layout` has size `0`.
+        let ptr = unsafe { alloc_for_layout(layout) };
+        if ptr.is_null() {
+            return Err(TryAllocError {
+                value: header,
+                error: TryReserveError::AllocError { layout },
+            });
+        }
+
+        // Frees the allocation and drops whatever has been initialized so
+        // far if a `Clone` impl panics partway through.
+        let mut guard = RawPartsGuard::<H, T> {
+            ptr,
+            layout,
+            header_written: false,
+            initialized: 0,
+            phantom: core::marker::PhantomData,
+        };
+
+        // SAFETY: `ptr` is non-null, freshly allocated, and large enough to
+        // hold `header` at offset `0`.
+        unsafe { ptr::write(ptr as *mut H, header) };
+        guard.header_written = true;
+
+        let items_ptr = unsafe { ptr.add(Self::items_offset()) as *mut T };
+        for (i, item) in items.iter().cloned().enumerate() {
+            // SAFETY: `i < len`, so `items_ptr.add(i)` is within the
+            // allocation and hasn't been written yet.
+            unsafe { ptr::write(items_ptr.add(i), item) };
+            guard.initialized = i + 1;
+        }
+
+        let ptr = guard.ptr;
+        mem::forget(guard);
+
+        // SAFETY: `ptr` now points to a fully-initialized header-slice of
+        // `len` elements, allocated with `Self::layout_for_len(len)`.
+        Ok(unsafe { Self::boxed_from_raw_parts(ptr as *mut H, len) })
+    }
+
+    /// Allocates a boxed header-slice with `len` trailing elements, leaving
+    /// the header and every element uninitialized.
+    ///
+    /// Write `header` and all `len` elements before calling [`assume_init`]
+    /// on the result.
+    ///
+    /// [`assume_init`]: HeaderSlice::assume_init
+    #[cfg(feature = "alloc")]
+    pub fn new_uninit(len: usize) -> Box<HeaderSlice<MaybeUninit<H>, MaybeUninit<T>>> {
+        let layout = Self::layout_for_len(len);
+
+        // SAFETY: `alloc_for_layout` returns a dangling pointer instead of
+        // calling into the allocator when `layout` has size `0`.
+        let ptr = unsafe { alloc_for_layout(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        // SAFETY: `ptr` is a fresh allocation sized and aligned for a
+        // header-slice of `len` elements; leaving it uninitialized is sound
+        // because every field here is `MaybeUninit`.
+        unsafe {
+            HeaderSlice::<MaybeUninit<H>, MaybeUninit<T>>::boxed_from_raw_parts(
+                ptr as *mut MaybeUninit<H>,
+                len,
+            )
+        }
+    }
+
+    /// Like [`new_uninit`], but zeroes the allocation first.
+    ///
+    /// [`new_uninit`]: HeaderSlice::new_uninit
+    #[cfg(feature = "alloc")]
+    pub fn new_zeroed(len: usize) -> Box<HeaderSlice<MaybeUninit<H>, MaybeUninit<T>>> {
+        let layout = Self::layout_for_len(len);
+
+        // SAFETY: `alloc_zeroed_for_layout` returns a dangling pointer
+        // instead of calling into the allocator when `layout` has size `0`.
+        let ptr = unsafe { alloc_zeroed_for_layout(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        // SAFETY: as above; an all-zero bit pattern is additionally always a
+        // valid `MaybeUninit<H>`/`MaybeUninit<T>` value.
+        unsafe {
+            HeaderSlice::<MaybeUninit<H>, MaybeUninit<T>>::boxed_from_raw_parts(
+                ptr as *mut MaybeUninit<H>,
+                len,
+            )
+        }
+    }
+}
+
+impl<H, T> HeaderSlice<MaybeUninit<H>, MaybeUninit<T>> {
+    /// Converts to `Box<HeaderSlice<H, T>>`.
+    ///
+    /// # Safety
+    ///
+    /// The header and every element of the trailing slice must have already
+    /// been initialized, e.g. after allocating with [`new_uninit`] and
+    /// writing to each of `header` and `slice`.
+    ///
+    /// [`new_uninit`]: HeaderSlice::new_uninit
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub unsafe fn assume_init(self: Box<Self>) -> Box<HeaderSlice<H, T>> {
+        // SAFETY: `MaybeUninit<H>`/`MaybeUninit<T>` share the size,
+        // alignment, and representation of `H`/`T`, so the two
+        // `HeaderSlice`s — and the fat pointers backing these boxes — are
+        // layout-identical; the caller guarantees every byte has actually
+        // been initialized.
+        mem::transmute(self)
+    }
 }
 
-// TODO: `From<Arc<H>>` for `Arc<HeaderSlice<H, H>>`
-// TODO: `From<Rc<H>>`  for `Rc<HeaderSlice<H, H>>`
+// `From<Arc<H>>`/`From<Rc<H>>` are implemented as `HeaderArc`/`HeaderRc`
+// conversions in the `rc` module, rather than producing `Arc`/`Rc` around
+// the (necessarily fat-pointer) `HeaderSlice<H, H>` DST directly.
 
 // TODO: `Clone` for `Box<HeaderSlice<H, T>>`
 
@@ -112,6 +414,44 @@ fn is_header_slice_aligned<H, T>(header: *const H) -> bool {
     header as usize % mem::align_of::<T>() == 0
 }
 
+/// Debug-asserts the documented safety preconditions shared by the
+/// `from_raw_parts`/`*_unchecked` family: a non-null, properly-aligned
+/// `header`, and a total size that doesn't overflow `usize` or exceed
+/// `isize::MAX`. Compiles to nothing in release builds: the overflow-
+/// checked arithmetic below is debug-only instrumentation, not part of the
+/// documented contract, so it must not become a release-mode panic path.
+#[cfg(debug_assertions)]
+#[inline]
+fn debug_assert_raw_parts_preconditions<H, T>(header: *const H, len: usize) {
+    debug_assert!(!header.is_null(), "header pointer must not be null");
+
+    let align = mem::align_of::<H>().max(mem::align_of::<T>());
+    debug_assert_eq!(
+        header as usize % align,
+        0,
+        "header must be aligned to {} (the greater of `align_of::<H>()` and `align_of::<T>()`)",
+        align,
+    );
+
+    let items_size = len
+        .checked_mul(mem::size_of::<T>())
+        .expect("header-slice size overflowed `usize`");
+    let size = HeaderSlice::<H, T>::items_offset()
+        .checked_add(items_size)
+        .expect("header-slice size overflowed `usize`");
+
+    debug_assert!(
+        size <= isize::MAX as usize,
+        "header-slice size must not exceed `isize::MAX`",
+    );
+}
+
+/// No-op release-mode counterpart to the `#[cfg(debug_assertions)]`
+/// `debug_assert_raw_parts_preconditions` above.
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+fn debug_assert_raw_parts_preconditions<H, T>(_header: *const H, _len: usize) {}
+
 impl<H, T> HeaderSlice<H, T> {
     /// Returns the result of calling `f` on a shared header-slice starting with
     /// `header`.
@@ -257,6 +597,9 @@ impl<H, T> HeaderSlice<H, T> {
     /// - The total size of the resulting header-slice must be no larger than
     ///   `isize::MAX`.
     ///
+    /// Debug builds assert the non-null, alignment, and size preconditions
+    /// above; this is skipped in release builds.
+    ///
     /// # Caveat
     ///
     /// The lifetime for the returned slice is inferred from its usage. To
@@ -269,6 +612,8 @@ impl<H, T> HeaderSlice<H, T> {
     /// [`UnsafeCell`]: https://doc.rust-lang.org/std/cell/struct.UnsafeCell.html
     #[inline]
     pub unsafe fn from_raw_parts<'a>(header: *const H, len: usize) -> &'a Self {
+        debug_assert_raw_parts_preconditions::<H, T>(header, len);
+
         // We never create `&[H]` because data past `header` may refer to
         // invalid instances of `H`. So instead we strictly use a raw slice
         // pointer.
@@ -302,6 +647,9 @@ impl<H, T> HeaderSlice<H, T> {
     /// - The total size of the resulting header-slice must be no larger than
     ///   `isize::MAX`.
     ///
+    /// Debug builds assert the non-null, alignment, and size preconditions
+    /// above; this is skipped in release builds.
+    ///
     /// # Caveat
     ///
     /// The lifetime for the returned slice is inferred from its usage. To
@@ -313,6 +661,8 @@ impl<H, T> HeaderSlice<H, T> {
     /// [valid]: https://doc.rust-lang.org/std/ptr/index.html#safety
     #[inline]
     pub unsafe fn from_raw_parts_mut<'a>(header: *mut H, len: usize) -> &'a mut Self {
+        debug_assert_raw_parts_preconditions::<H, T>(header, len);
+
         // We never create `&mut [H]` because data past `header` may refer to
         // invalid instances of `H`. So instead we strictly use a raw slice
         // pointer.
@@ -326,6 +676,10 @@ impl<H, T> HeaderSlice<H, T> {
     /// `header` must point to a header-slice with a slice of `len` items that
     /// has been allocated by the global allocator.
     ///
+    /// Debug builds assert that `header` is non-null, properly aligned, and
+    /// that the resulting size doesn't overflow; this is skipped in release
+    /// builds.
+    ///
     /// Improper use can lead to:
     ///
     /// - A double-free if the function is called twice on the same raw pointer.
@@ -336,11 +690,111 @@ impl<H, T> HeaderSlice<H, T> {
     #[cfg(feature = "alloc")]
     #[inline]
     pub unsafe fn boxed_from_raw_parts(header: *mut H, len: usize) -> Box<Self> {
+        debug_assert_raw_parts_preconditions::<H, T>(header, len);
+
         // We never create `&mut [H]` because data past `header` may refer to
         // invalid instances of `H`. So instead we strictly use a raw slice
         // pointer.
         Box::from_raw(ptr::slice_from_raw_parts_mut(header, len) as *mut Self)
     }
+
+    /// Creates a boxed header-slice by allocating space for `header` followed
+    /// by a clone of each element of `items`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn from_header_and_slice(header: H, items: &[T]) -> Box<Self>
+    where
+        T: Clone,
+    {
+        Self::from_header_and_iter(header, items.iter().cloned())
+    }
+
+    /// Creates a boxed header-slice by allocating space for `header` followed
+    /// by the elements yielded by `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` yields a different number of elements than what its
+    /// `ExactSizeIterator::len` reported.
+    #[cfg(feature = "alloc")]
+    pub fn from_header_and_iter<I>(header: H, items: I) -> Box<Self>
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        let len = items.len();
+        let layout = Self::layout_for_len(len);
+
+        // SAFETY: `alloc_for_layout` returns a dangling pointer instead of
+        // calling into the allocator when `layout` has size `0` (e.g. a
+        // zero-sized `H` with no trailing elements).
+        let ptr = unsafe { alloc_for_layout(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        // Frees the allocation and drops whatever has been initialized so
+        // far if a `Clone`/`Iterator` impl panics partway through.
+        let mut guard = RawPartsGuard::<H, T> {
+            ptr,
+            layout,
+            header_written: false,
+            initialized: 0,
+            phantom: core::marker::PhantomData,
+        };
+
+        // SAFETY: `ptr` is non-null, freshly allocated, and large enough to
+        // hold `header` at offset `0`.
+        unsafe { ptr::write(ptr as *mut H, header) };
+        guard.header_written = true;
+
+        let items_ptr = unsafe { ptr.add(Self::items_offset()) as *mut T };
+        for (i, item) in items.enumerate() {
+            assert!(i < len, "iterator yielded more elements than its length");
+
+            // SAFETY: `i < len`, so `items_ptr.add(i)` is within the
+            // allocation and hasn't been written yet.
+            unsafe { ptr::write(items_ptr.add(i), item) };
+            guard.initialized = i + 1;
+        }
+        assert_eq!(guard.initialized, len, "iterator yielded fewer elements than its length");
+
+        let ptr = guard.ptr;
+        mem::forget(guard);
+
+        // SAFETY: `ptr` now points to a fully-initialized header-slice of
+        // `len` elements, allocated with `Self::layout_for_len(len)`.
+        unsafe { Self::boxed_from_raw_parts(ptr as *mut H, len) }
+    }
+}
+
+/// Frees a header-slice allocation and drops whichever parts of it have been
+/// initialized so far, used to stay panic-safe while filling the allocation
+/// made by [`HeaderSlice::from_header_and_iter`].
+#[cfg(feature = "alloc")]
+struct RawPartsGuard<H, T> {
+    ptr: *mut u8,
+    layout: Layout,
+    header_written: bool,
+    initialized: usize,
+    phantom: core::marker::PhantomData<(H, T)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<H, T> Drop for RawPartsGuard<H, T> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.header_written {
+                ptr::drop_in_place(self.ptr as *mut H);
+            }
+
+            let items_ptr = self.ptr.add(HeaderSlice::<H, T>::items_offset()) as *mut T;
+            for i in 0..self.initialized {
+                ptr::drop_in_place(items_ptr.add(i));
+            }
+
+            dealloc_for_layout(self.ptr, self.layout);
+        }
+    }
 }
 
 impl<H> HeaderSlice<H, H> {
@@ -387,6 +841,26 @@ impl<H> HeaderSlice<H, H> {
         }
     }
 
+    /// Like [`from_full_boxed_slice`], but returns `slice` back instead of
+    /// `None` when it's empty, for parity with the `try_*` constructors'
+    /// error-carrying style.
+    ///
+    /// This reinterprets `slice`'s own allocation rather than allocating, so
+    /// unlike the other `try_*` constructors it can't fail due to the
+    /// allocator.
+    ///
+    /// [`from_full_boxed_slice`]: Self::from_full_boxed_slice
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn try_from_full_boxed_slice(slice: Box<[H]>) -> Result<Box<Self>, Box<[H]>> {
+        if slice.is_empty() {
+            Err(slice)
+        } else {
+            // SAFETY: `slice` has an element for a header.
+            Ok(unsafe { Self::from_full_boxed_slice_unchecked(slice) })
+        }
+    }
+
     /// Creates a shared header-slice from `slice`, using the first element as
     /// the header without checking if it exists.
     ///
@@ -452,3 +926,320 @@ impl<H> HeaderSlice<H, H> {
         unsafe { Box::from_raw(slice::from_raw_parts_mut(data, len)) }
     }
 }
+
+/// # Safety
+///
+/// Implementing this for `Self` asserts that every byte of `Self`'s
+/// in-memory representation belongs to some field — i.e. `Self` has no
+/// padding bytes.
+///
+/// This is a stronger guarantee than [`Copy`]: a `#[derive(Copy)]` struct
+/// can still contain padding (e.g. `struct Hdr { a: u8, b: u64 }` has 7
+/// padding bytes after `a`), and reading that padding through a `&[u8]`
+/// view, as [`HeaderSlice::as_io_slices`] does, would expose uninitialized
+/// memory — undefined behavior.
+#[cfg(feature = "std")]
+pub unsafe trait NoPadding: Copy {}
+
+/// # Safety
+///
+/// In addition to the [`NoPadding`] requirement, implementing this for
+/// `Self` asserts that every possible bit pattern is a valid value of
+/// `Self`. Types with validity invariants — `bool`, `char`, `NonZeroU32`,
+/// and enums in general — must not implement this, since overwriting their
+/// bytes with arbitrary data, as [`HeaderSlice::as_io_slices_mut`]'s
+/// [`Read::read_vectored`] use case does, could produce an invalid
+/// instance.
+///
+/// [`Read::read_vectored`]: std::io::Read::read_vectored
+#[cfg(feature = "std")]
+pub unsafe trait AnyBitPattern: NoPadding {}
+
+#[cfg(feature = "std")]
+unsafe impl NoPadding for u8 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for u16 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for u32 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for u64 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for u128 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for usize {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for i8 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for i16 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for i32 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for i64 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for i128 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for isize {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for f32 {}
+#[cfg(feature = "std")]
+unsafe impl NoPadding for f64 {}
+
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for u8 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for u16 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for u32 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for u64 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for u128 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for usize {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for i8 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for i16 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for i32 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for i64 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for i128 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for isize {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for f32 {}
+#[cfg(feature = "std")]
+unsafe impl AnyBitPattern for f64 {}
+
+/// Treats a byte-payload header-slice as a framed buffer: a fixed `header`
+/// followed by a `[u8]` payload, for use with vectored I/O.
+#[cfg(feature = "std")]
+impl<H> HeaderSlice<H, u8>
+where
+    H: NoPadding,
+{
+    /// Returns `self` as two vectored-I/O buffers: `header`'s raw bytes,
+    /// followed by the trailing byte payload.
+    ///
+    /// This lets the whole header-slice be handed to
+    /// [`Write::write_vectored`] without copying into a contiguous staging
+    /// buffer.
+    ///
+    /// `H: NoPadding` is required so every byte of `header` is guaranteed to
+    /// be initialized; exposing padding bytes through an [`IoSlice`] would
+    /// be undefined behavior. `Copy` alone doesn't rule out padding (e.g. a
+    /// `#[derive(Copy)] struct Hdr { a: u8, b: u64 }`), which is why this
+    /// needs the stronger [`NoPadding`] bound.
+    ///
+    /// [`Write::write_vectored`]: std::io::Write::write_vectored
+    #[inline]
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 2] {
+        let header = &self.header as *const H as *const u8;
+
+        // SAFETY: `header` is valid for `size_of::<H>()` bytes, and
+        // `H: NoPadding` guarantees every one of those bytes is initialized.
+        let header = unsafe { slice::from_raw_parts(header, mem::size_of::<H>()) };
+
+        [IoSlice::new(header), IoSlice::new(&self.slice)]
+    }
+}
+
+/// Treats a byte-payload header-slice as a framed buffer: a fixed `header`
+/// followed by a `[u8]` payload, for use with vectored I/O.
+#[cfg(feature = "std")]
+impl<H> HeaderSlice<H, u8>
+where
+    H: AnyBitPattern,
+{
+    /// Returns `self` as two mutable vectored-I/O buffers: `header`'s raw
+    /// bytes, followed by the trailing byte payload.
+    ///
+    /// This lets the whole header-slice be handed to
+    /// [`Read::read_vectored`] without copying into a contiguous staging
+    /// buffer.
+    ///
+    /// `H: AnyBitPattern` (rather than just [`NoPadding`]) is required
+    /// because `read_vectored` may overwrite `header`'s bytes with
+    /// arbitrary data; for a type with validity invariants (`bool`, `char`,
+    /// `NonZero*`, enums) that could construct an invalid value, which is
+    /// undefined behavior.
+    ///
+    /// [`Read::read_vectored`]: std::io::Read::read_vectored
+    #[inline]
+    pub fn as_io_slices_mut(&mut self) -> [IoSliceMut<'_>; 2] {
+        let header = &mut self.header as *mut H as *mut u8;
+
+        // SAFETY: as above, plus `H: AnyBitPattern` guarantees any bytes
+        // `read_vectored` writes back form a valid `H`.
+        let header = unsafe { slice::from_raw_parts_mut(header, mem::size_of::<H>()) };
+
+        [IoSliceMut::new(header), IoSliceMut::new(&mut self.slice)]
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::HeaderSlice;
+
+    // Over-aligned relative to `u8`, so a header-slice with a few `u8` items
+    // ends in trailing padding up to a multiple of 16 bytes, which a missing
+    // `pad_to_align()` in `layout_for_len` would drop when computing the
+    // allocation size, while `Layout::for_value` (used by `Box`'s `Drop`)
+    // would still include it.
+    #[derive(Debug)]
+    #[repr(align(16))]
+    struct OverAligned(u64);
+
+    #[test]
+    fn new_uninit_round_trip_drops_without_layout_mismatch() {
+        let _guard = crate::test_support::lock();
+
+        let len = 3;
+        let mut boxed = HeaderSlice::<OverAligned, u8>::new_uninit(len);
+        boxed.header.write(OverAligned(7));
+        for (i, slot) in boxed.slice.iter_mut().enumerate() {
+            slot.write(i as u8);
+        }
+
+        // SAFETY: the header and every element were just initialized above.
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(boxed.header.0, 7);
+        assert_eq!(&boxed.slice, &[0, 1, 2]);
+
+        // Dropping runs the allocator's `dealloc`, which panics through the
+        // tracking allocator in `test_support` if the `Layout` used to
+        // allocate doesn't match the one `Box`'s `Drop` computes.
+        drop(boxed);
+    }
+
+    #[test]
+    fn from_header_and_slice_round_trip_drops_without_layout_mismatch() {
+        let _guard = crate::test_support::lock();
+
+        let boxed = HeaderSlice::<OverAligned, u8>::from_header_and_slice(OverAligned(9), &[1, 2, 3]);
+        assert_eq!(boxed.header.0, 9);
+        assert_eq!(&boxed.slice, &[1, 2, 3]);
+
+        drop(boxed);
+    }
+
+    #[test]
+    fn try_from_header_and_slice_round_trip_drops_without_layout_mismatch() {
+        let _guard = crate::test_support::lock();
+
+        let boxed =
+            HeaderSlice::<OverAligned, u8>::try_from_header_and_slice(OverAligned(9), &[1, 2, 3])
+                .unwrap();
+        assert_eq!(boxed.header.0, 9);
+        assert_eq!(&boxed.slice, &[1, 2, 3]);
+
+        drop(boxed);
+    }
+
+    #[test]
+    fn from_header_and_iter_panic_safety() {
+        let _guard = crate::test_support::lock();
+
+        use std::panic::{self, AssertUnwindSafe};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounted(#[allow(dead_code)] u32);
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        impl Drop for DropCounted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        // Yields 3 elements, then panics instead of yielding a 4th, while
+        // its `ExactSizeIterator::len()` still claims 5 — exercising
+        // `RawPartsGuard`'s cleanup of a partially-initialized allocation.
+        struct PanicOnFourth {
+            next: u32,
+        }
+
+        impl Iterator for PanicOnFourth {
+            type Item = DropCounted;
+
+            fn next(&mut self) -> Option<DropCounted> {
+                assert!(self.next < 3, "iterator panics before yielding a 4th item");
+                let item = DropCounted(self.next);
+                self.next += 1;
+                Some(item)
+            }
+        }
+
+        impl ExactSizeIterator for PanicOnFourth {
+            fn len(&self) -> usize {
+                5
+            }
+        }
+
+        DROPS.store(0, Ordering::SeqCst);
+
+        // Silences the default panic hook for the expected panic below: it
+        // otherwise prints (and, under `RUST_BACKTRACE`, captures) a
+        // backtrace, which allocates far more than this test's actual
+        // allocation under test and has nothing to do with what's being
+        // verified here.
+        let prev_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            HeaderSlice::<DropCounted, DropCounted>::from_header_and_iter(
+                DropCounted(100),
+                PanicOnFourth { next: 0 },
+            )
+        }));
+        panic::set_hook(prev_hook);
+
+        assert!(result.is_err(), "expected the iterator to panic");
+
+        // The header and the 3 items written before the panic must each be
+        // dropped exactly once by the panic-safety guard (and the
+        // allocation freed, checked by the tracking allocator's `dealloc`).
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1 + 3);
+    }
+
+    #[test]
+    fn as_io_slices_contains_header_then_payload_bytes() {
+        let boxed = HeaderSlice::<u32, u8>::from_header_and_slice(0x04030201, &[0xAA, 0xBB]);
+        let slices = boxed.as_io_slices();
+
+        assert_eq!(&*slices[0], &0x04030201u32.to_ne_bytes());
+        assert_eq!(&*slices[1], &[0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn as_io_slices_mut_overwrites_header_and_payload_bytes() {
+        let mut boxed = HeaderSlice::<u32, u8>::from_header_and_slice(0, &[0, 0]);
+
+        {
+            let mut slices = boxed.as_io_slices_mut();
+            slices[0].copy_from_slice(&0x0A0B0C0Du32.to_ne_bytes());
+            slices[1].copy_from_slice(&[1, 2]);
+        }
+
+        assert_eq!(boxed.header, 0x0A0B0C0D);
+        assert_eq!(&boxed.slice, &[1, 2]);
+    }
+
+    #[test]
+    fn new_zeroed_round_trip_drops_without_layout_mismatch() {
+        let _guard = crate::test_support::lock();
+
+        let len = 2;
+        let boxed = HeaderSlice::<OverAligned, u8>::new_zeroed(len);
+
+        // SAFETY: an all-zero bit pattern is a valid `MaybeUninit<OverAligned>`
+        // and a valid `MaybeUninit<u8>`; `new_zeroed` guarantees this.
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(boxed.header.0, 0);
+        assert_eq!(&boxed.slice, &[0, 0]);
+
+        drop(boxed);
+    }
+}
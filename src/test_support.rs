@@ -0,0 +1,116 @@
+//! A tracking `#[global_allocator]` shared by this crate's `#[cfg(test)]`
+//! modules, used to catch alloc/dealloc [`Layout`] mismatches — the
+//! exact class of bug `pad_to_align` in `slice.rs` guards against — without
+//! pulling in an external dependency.
+//!
+//! Every allocation in the test binary goes through this, not just the ones
+//! under test, so [`lock`] must be held for the duration of any test that
+//! cares about the allocator's bookkeeping; this keeps concurrently-running
+//! tests from stepping on each other's slots.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+const SLOTS: usize = 1024;
+
+struct Tracking;
+
+#[global_allocator]
+static GLOBAL: Tracking = Tracking;
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+// The `INIT` consts below exist only to repeat-initialize a `static` array
+// element-by-element; they're never read as shared constants themselves.
+#[allow(clippy::declare_interior_mutable_const)]
+static PTRS: [AtomicUsize; SLOTS] = {
+    const INIT: AtomicUsize = AtomicUsize::new(0);
+    [INIT; SLOTS]
+};
+#[allow(clippy::declare_interior_mutable_const)]
+static SIZES: [AtomicUsize; SLOTS] = {
+    const INIT: AtomicUsize = AtomicUsize::new(0);
+    [INIT; SLOTS]
+};
+#[allow(clippy::declare_interior_mutable_const)]
+static ALIGNS: [AtomicUsize; SLOTS] = {
+    const INIT: AtomicUsize = AtomicUsize::new(0);
+    [INIT; SLOTS]
+};
+
+/// Serializes a test's use of the tracking allocator's bookkeeping against
+/// other such tests.
+pub(crate) fn lock() -> MutexGuard<'static, ()> {
+    TEST_LOCK.lock().unwrap_or_else(|poison| poison.into_inner())
+}
+
+fn record(ptr: *mut u8, layout: Layout) {
+    if ptr.is_null() {
+        return;
+    }
+
+    for i in 0..SLOTS {
+        if PTRS[i]
+            .compare_exchange(0, ptr as usize, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            SIZES[i].store(layout.size(), Ordering::Release);
+            ALIGNS[i].store(layout.align(), Ordering::Release);
+            return;
+        }
+    }
+
+    panic!("test tracking allocator ran out of slots");
+}
+
+/// Panics if `ptr` was previously recorded with a different size or
+/// alignment than `layout`, then forgets it.
+fn check_and_forget(ptr: *mut u8, layout: Layout) {
+    for i in 0..SLOTS {
+        if PTRS[i].load(Ordering::Acquire) == ptr as usize {
+            let size = SIZES[i].load(Ordering::Acquire);
+            let align = ALIGNS[i].load(Ordering::Acquire);
+
+            assert_eq!(
+                size,
+                layout.size(),
+                "dealloc size didn't match the size it was allocated with (Layout mismatch)",
+            );
+            assert_eq!(
+                align,
+                layout.align(),
+                "dealloc align didn't match the align it was allocated with (Layout mismatch)",
+            );
+
+            PTRS[i].store(0, Ordering::Release);
+            return;
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for Tracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        record(ptr, layout);
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        record(ptr, layout);
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        check_and_forget(ptr, layout);
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        check_and_forget(ptr, layout);
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        record(new_ptr, Layout::from_size_align_unchecked(new_size, layout.align()));
+        new_ptr
+    }
+}